@@ -1,59 +1,133 @@
-use rusqlite::{Connection, Result};
+use rusqlite::{Connection, Result, Transaction};
 use std::path::{Path, PathBuf};
 
-pub fn cleanup_database(db_path: &PathBuf) -> Result<()> {
+use super::exclude::ExcludeList;
+
+/// Default retention window, in days: history rows older than this are
+/// purged regardless of how often the path was visited.
+pub const DEFAULT_RETENTION_DAYS: i64 = 90;
+
+/// Mirrors zoxide's AGE_THRESHOLD: once a table's total visit weight
+/// crosses this, each path's visit count is scaled by `max_age * 0.9 / total`
+/// and the paths left with less than one visit are dropped entirely; the
+/// rows of the remaining paths are kept as they are.
+pub const DEFAULT_MAX_AGE: f64 = 9000.0;
+
+pub fn cleanup_database(
+    db_path: &PathBuf,
+    retention_days: i64,
+    max_age: f64,
+    excludes: &ExcludeList,
+) -> Result<()> {
     let mut conn = Connection::open(db_path)?;
     let tx = conn.transaction()?;
-    
-    // Cleanup directories
-    let dirs_to_remove = {
-        let mut stmt = tx.prepare("SELECT id, path FROM directory_history")?;
-        let dir_iter = stmt.query_map([], |row| {
+
+    prune_table(&tx, "directory_history", retention_days, max_age, excludes)?;
+    prune_table(&tx, "file_history", retention_days, max_age, excludes)?;
+
+    tx.commit()?;
+
+    Ok(())
+}
+
+fn prune_table(
+    tx: &Transaction,
+    table: &str,
+    retention_days: i64,
+    max_age: f64,
+    excludes: &ExcludeList,
+) -> Result<()> {
+    // Drop rows whose path no longer exists on disk.
+    let dead_ids = {
+        let mut stmt = tx.prepare(&format!("SELECT id, path FROM {table}"))?;
+        let rows = stmt.query_map([], |row| {
             Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
         })?;
 
-        let mut to_remove = Vec::new();
-        for entry in dir_iter {
-            let (id, path) = entry?;
+        let mut ids = Vec::new();
+        for row in rows {
+            let (id, path) = row?;
             if !Path::new(&path).exists() {
-                to_remove.push(id);
+                ids.push(id);
             }
         }
-        to_remove
+        ids
     };
+    delete_ids(tx, table, &dead_ids)?;
 
-    if !dirs_to_remove.is_empty() {
-        let mut delete_dir = tx.prepare("DELETE FROM directory_history WHERE id = ?")?;
-        for id in dirs_to_remove {
-            delete_dir.execute([id])?;
+    // Same transaction, same sweep: purge anything matching an exclusion
+    // pattern so excluded paths drain out of the database over time
+    // instead of needing a dedicated full scan.
+    if !excludes.is_empty() {
+        let excluded_paths = {
+            let mut stmt = tx.prepare(&format!("SELECT DISTINCT path FROM {table}"))?;
+            let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+
+            let mut paths = Vec::new();
+            for row in rows {
+                let path = row?;
+                if excludes.matches(&path) {
+                    paths.push(path);
+                }
+            }
+            paths
+        };
+
+        let mut delete_path = tx.prepare(&format!("DELETE FROM {table} WHERE path = ?1"))?;
+        for path in excluded_paths {
+            delete_path.execute([path])?;
         }
     }
-    
-    // Cleanup files
-    let files_to_remove = {
-        let mut stmt = tx.prepare("SELECT id, path FROM file_history")?;
-        let file_iter = stmt.query_map([], |row| {
-            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
-        })?;
 
-        let mut to_remove = Vec::new();
-        for entry in file_iter {
-            let (id, path) = entry?;
-            if !Path::new(&path).exists() {
-                to_remove.push(id);
+    // Drop anything outside the retention window outright.
+    tx.execute(
+        &format!("DELETE FROM {table} WHERE timestamp < datetime('now', ?1)"),
+        [format!("-{retention_days} days")],
+    )?;
+
+    // If the table has grown past max_age, scale every path's weight down
+    // and drop whatever paths that leaves with less than one effective visit.
+    // Only those paths' rows are deleted; the other paths keep all their rows.
+    let total: i64 = tx.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| row.get(0))?;
+    if total as f64 > max_age {
+        let scale = max_age * 0.9 / total as f64;
+
+        let light_paths = {
+            let mut stmt = tx.prepare(&format!(
+                "SELECT path, COUNT(*) as weight FROM {table} GROUP BY path"
+            ))?;
+            let weighted = stmt.query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })?;
+
+            let mut paths = Vec::new();
+            for row in weighted {
+                let (path, weight) = row?;
+                if weight as f64 * scale < 1.0 {
+                    paths.push(path);
+                }
             }
-        }
-        to_remove
-    };
+            paths
+        };
 
-    if !files_to_remove.is_empty() {
-        let mut delete_file = tx.prepare("DELETE FROM file_history WHERE id = ?")?;
-        for id in files_to_remove {
-            delete_file.execute([id])?;
+        let mut delete_path = tx.prepare(&format!("DELETE FROM {table} WHERE path = ?1"))?;
+        for path in light_paths {
+            delete_path.execute([path])?;
         }
     }
 
-    tx.commit()?;
-    
+    Ok(())
+}
+
+fn delete_ids(tx: &Transaction, table: &str, ids: &[i64]) -> Result<()> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut stmt = tx.prepare(&format!("DELETE FROM {table} WHERE id = ?"))?;
+    for id in ids {
+        stmt.execute([*id])?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,149 @@
+use rusqlite::{Connection, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::hash::Hasher;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use siphasher::sip128::{Hasher128, SipHasher13};
+
+use super::queries::FileEntry;
+
+const HASH_BLOCK_SIZE: usize = 4096;
+
+/// Finds groups of two or more files from `file_history` that share the
+/// same content. Files are bucketed by size first (a `stat`, not a read),
+/// then disambiguated with a partial hash over just the first block, and
+/// only files still colliding on that get a full-content hash — so a
+/// size group whose files already differ in their first block costs one
+/// block read per file, not a full read.
+pub fn duplicates(db_path: &PathBuf) -> Result<Vec<Vec<FileEntry>>> {
+    let conn = Connection::open(db_path)?;
+    prune_missing_files(&conn)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT path, file_type, action, COUNT(*) as opens,
+                datetime(MAX(timestamp), 'localtime') as last_opened
+         FROM file_history
+         GROUP BY path, file_type, action"
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok(FileEntry {
+            path: row.get(0)?,
+            file_type: row.get(1)?,
+            action: row.get(2)?,
+            opens: Some(row.get(3)?),
+            timestamp: Some(row.get(4)?),
+            score: None,
+        })
+    })?.collect::<Result<Vec<_>>>()?;
+
+    let mut seen_paths = HashSet::new();
+    let candidates: Vec<FileEntry> = rows
+        .into_iter()
+        .filter(|entry| Path::new(&entry.path).exists() && seen_paths.insert(entry.path.clone()))
+        .collect();
+
+    Ok(group_duplicates(candidates))
+}
+
+/// Mirrors `cleanup_database`'s dead-path sweep, scoped to `file_history`,
+/// so a `duplicates` run also drains entries for files that vanished
+/// since they were recorded.
+fn prune_missing_files(conn: &Connection) -> Result<()> {
+    let stale_ids = {
+        let mut stmt = conn.prepare("SELECT id, path FROM file_history")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut ids = Vec::new();
+        for row in rows {
+            let (id, path) = row?;
+            if !Path::new(&path).exists() {
+                ids.push(id);
+            }
+        }
+        ids
+    };
+
+    if !stale_ids.is_empty() {
+        let mut stmt = conn.prepare("DELETE FROM file_history WHERE id = ?")?;
+        for id in stale_ids {
+            stmt.execute([id])?;
+        }
+    }
+
+    Ok(())
+}
+
+fn group_duplicates(candidates: Vec<FileEntry>) -> Vec<Vec<FileEntry>> {
+    let mut by_size: HashMap<u64, Vec<FileEntry>> = HashMap::new();
+    for entry in candidates {
+        if let Ok(metadata) = fs::metadata(&entry.path) {
+            by_size.entry(metadata.len()).or_default().push(entry);
+        }
+    }
+
+    let mut groups = Vec::new();
+    for same_size in by_size.into_values() {
+        if same_size.len() < 2 {
+            continue; // unique size: no possible duplicate, skip hashing entirely
+        }
+
+        let mut by_partial: HashMap<u128, Vec<FileEntry>> = HashMap::new();
+        for entry in same_size {
+            if let Some(hash) = partial_hash(&entry.path) {
+                by_partial.entry(hash).or_default().push(entry);
+            }
+        }
+
+        for same_partial in by_partial.into_values() {
+            if same_partial.len() < 2 {
+                continue; // first-block hash already disambiguates this bucket
+            }
+
+            let mut by_full: HashMap<u128, Vec<FileEntry>> = HashMap::new();
+            for entry in same_partial {
+                if let Some(hash) = full_hash(&entry.path) {
+                    by_full.entry(hash).or_default().push(entry);
+                }
+            }
+
+            for same_full in by_full.into_values() {
+                if same_full.len() >= 2 {
+                    groups.push(same_full);
+                }
+            }
+        }
+    }
+
+    groups
+}
+
+fn partial_hash(path: &str) -> Option<u128> {
+    let mut file = File::open(path).ok()?;
+    let mut buf = [0u8; HASH_BLOCK_SIZE];
+    let n = file.read(&mut buf).ok()?;
+
+    let mut hasher = SipHasher13::new();
+    hasher.write(&buf[..n]);
+    Some(hasher.finish128().as_u128())
+}
+
+fn full_hash(path: &str) -> Option<u128> {
+    let mut file = File::open(path).ok()?;
+    let mut hasher = SipHasher13::new();
+    let mut buf = [0u8; HASH_BLOCK_SIZE];
+
+    loop {
+        let n = file.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        hasher.write(&buf[..n]);
+    }
+
+    Some(hasher.finish128().as_u128())
+}
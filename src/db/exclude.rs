@@ -0,0 +1,79 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use glob::Pattern;
+
+/// Paths matching any of these globs are suppressed from query results and
+/// lazily purged from the database by `cleanup_database`.
+#[derive(Debug, Default, Clone)]
+pub struct ExcludeList {
+    patterns: Vec<Pattern>,
+}
+
+impl ExcludeList {
+    pub fn new(patterns: Vec<Pattern>) -> Self {
+        ExcludeList { patterns }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        self.patterns.iter().any(|pattern| pattern.matches(path))
+    }
+}
+
+pub fn get_default_excludes_path() -> PathBuf {
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".fzf-nav").join("excludes")
+}
+
+/// Expands a leading `~/` or `$HOME` in a pattern to the real home
+/// directory, since stored DB paths are always absolute — a pattern like
+/// `~/.cache` would otherwise never match anything.
+///
+/// Note this only matches the literal path: excluding a whole subtree
+/// (e.g. everything under `/tmp`) needs `/tmp/**`, not `/tmp`.
+fn expand_home(pattern: &str, home: &str) -> String {
+    if let Some(rest) = pattern.strip_prefix("~/") {
+        format!("{home}/{rest}")
+    } else if let Some(rest) = pattern.strip_prefix("$HOME/") {
+        format!("{home}/{rest}")
+    } else {
+        pattern.to_string()
+    }
+}
+
+/// Builds the exclusion list from `--exclude` flags plus, if it exists, the
+/// `~/.fzf-nav/excludes` file (one glob pattern per line; blank lines and
+/// `#`-prefixed lines are ignored).
+pub fn load_excludes(from_flags: &[String], excludes_path: &PathBuf) -> ExcludeList {
+    let mut raw: Vec<String> = from_flags.to_vec();
+
+    if let Ok(contents) = fs::read_to_string(excludes_path) {
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            raw.push(line.to_string());
+        }
+    }
+
+    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    let patterns = raw
+        .into_iter()
+        .map(|pattern| expand_home(&pattern, &home))
+        .filter_map(|pattern| match Pattern::new(&pattern) {
+            Ok(p) => Some(p),
+            Err(e) => {
+                eprintln!("Warning: ignoring invalid exclude pattern {:?}: {}", pattern, e);
+                None
+            }
+        })
+        .collect();
+
+    ExcludeList::new(patterns)
+}
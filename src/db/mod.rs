@@ -1,7 +1,14 @@
 pub mod queries;
 pub mod utils;
 pub mod cleanup;
+pub mod exclude;
+pub mod dedupe;
 
-pub use queries::{file_stats, frequent_dirs, recent_dirs, recent_files, search_history};
-pub use cleanup::cleanup_database;
+pub use queries::{
+    file_stats, frecent_dirs, frecent_files, jump_dirs, popular_dirs, recent_dirs, recent_files,
+    search_history, DirectoryEntry, FileEntry, FileStats, SearchResult,
+};
+pub use cleanup::{cleanup_database, DEFAULT_MAX_AGE, DEFAULT_RETENTION_DAYS};
 pub use utils::get_default_db_path;
+pub use exclude::{get_default_excludes_path, load_excludes, ExcludeList};
+pub use dedupe::duplicates;
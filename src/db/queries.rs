@@ -0,0 +1,331 @@
+use rusqlite::{Connection, Result};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+use super::exclude::ExcludeList;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirectoryEntry {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visits: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileEntry {
+    pub path: String,
+    pub file_type: String,
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opens: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileStats {
+    pub file_type: String,
+    pub action: String,
+    pub opens: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub directories: Vec<DirectoryEntry>,
+    pub files: Vec<FileEntry>,
+}
+
+// Shared by the frecent-* queries: boosts raw visit counts by how
+// recently the path was touched, the same bucketing zoxide uses.
+const FRECENCY_CASE: &str = "(CASE
+                    WHEN (julianday('now') - julianday(MAX(timestamp))) * 24 < 1 THEN 4.0
+                    WHEN (julianday('now') - julianday(MAX(timestamp))) < 1 THEN 2.0
+                    WHEN (julianday('now') - julianday(MAX(timestamp))) < 7 THEN 0.5
+                    ELSE 0.25
+                END)";
+
+/// Splits `entries` into the ones that survive `excludes` and the distinct
+/// paths that didn't, so callers can purge the latter from `table` instead
+/// of just filtering them out of this one result set.
+fn split_excluded<T>(
+    entries: Vec<T>,
+    excludes: &ExcludeList,
+    path_of: impl Fn(&T) -> &str,
+) -> (Vec<T>, HashSet<String>) {
+    if excludes.is_empty() {
+        return (entries, HashSet::new());
+    }
+
+    let mut kept = Vec::new();
+    let mut excluded_paths = HashSet::new();
+    for entry in entries {
+        if excludes.matches(path_of(&entry)) {
+            excluded_paths.insert(path_of(&entry).to_string());
+        } else {
+            kept.push(entry);
+        }
+    }
+    (kept, excluded_paths)
+}
+
+/// Lazily deletes rows for `paths` from `table`, the same purge
+/// `cleanup_database` does for exclusions, triggered here so excluded
+/// paths drain out of the database as they're encountered instead of
+/// lingering until the next cleanup.
+fn purge_excluded(conn: &Connection, table: &str, paths: &HashSet<String>) -> Result<()> {
+    if paths.is_empty() {
+        return Ok(());
+    }
+
+    let mut stmt = conn.prepare(&format!("DELETE FROM {table} WHERE path = ?1"))?;
+    for path in paths {
+        stmt.execute([path])?;
+    }
+    Ok(())
+}
+
+pub fn recent_dirs(db_path: &PathBuf, limit: i32, excludes: &ExcludeList) -> Result<Vec<DirectoryEntry>> {
+    let conn = Connection::open(db_path)?;
+    let mut stmt = conn.prepare(
+        "SELECT path, datetime(timestamp, 'localtime') as visited
+         FROM (
+             SELECT * FROM directory_history
+             ORDER BY timestamp DESC
+             LIMIT ?1
+         )
+         ORDER BY timestamp ASC"
+    )?;
+
+    let entries = stmt.query_map([limit], |row| {
+        Ok(DirectoryEntry {
+            path: row.get(0)?,
+            timestamp: Some(row.get(1)?),
+            visits: None,
+            score: None,
+        })
+    })?.collect::<Result<Vec<_>>>()?;
+
+    let (kept, excluded) = split_excluded(entries, excludes, |e| &e.path);
+    purge_excluded(&conn, "directory_history", &excluded)?;
+    Ok(kept)
+}
+
+pub fn recent_files(db_path: &PathBuf, limit: i32, excludes: &ExcludeList) -> Result<Vec<FileEntry>> {
+    let conn = Connection::open(db_path)?;
+    let mut stmt = conn.prepare(
+        "SELECT path, file_type, action, datetime(timestamp, 'localtime') as opened
+         FROM (
+             SELECT * FROM file_history
+             ORDER BY timestamp DESC
+             LIMIT ?1
+         )
+         ORDER BY timestamp ASC"
+    )?;
+
+    let entries = stmt.query_map([limit], |row| {
+        Ok(FileEntry {
+            path: row.get(0)?,
+            file_type: row.get(1)?,
+            action: row.get(2)?,
+            timestamp: Some(row.get(3)?),
+            opens: None,
+            score: None,
+        })
+    })?.collect::<Result<Vec<_>>>()?;
+
+    let (kept, excluded) = split_excluded(entries, excludes, |e| &e.path);
+    purge_excluded(&conn, "file_history", &excluded)?;
+    Ok(kept)
+}
+
+pub fn popular_dirs(db_path: &PathBuf, limit: i32, excludes: &ExcludeList) -> Result<Vec<DirectoryEntry>> {
+    let conn = Connection::open(db_path)?;
+    let mut stmt = conn.prepare(
+        "SELECT path, COUNT(*) as visits,
+                datetime(MAX(timestamp), 'localtime') as last_visited
+         FROM directory_history
+         GROUP BY path
+         ORDER BY visits DESC
+         LIMIT ?1"
+    )?;
+
+    let entries = stmt.query_map([limit], |row| {
+        Ok(DirectoryEntry {
+            path: row.get(0)?,
+            visits: Some(row.get(1)?),
+            timestamp: Some(row.get(2)?),
+            score: None,
+        })
+    })?.collect::<Result<Vec<_>>>()?;
+
+    let (kept, excluded) = split_excluded(entries, excludes, |e| &e.path);
+    purge_excluded(&conn, "directory_history", &excluded)?;
+    Ok(kept)
+}
+
+/// Like `popular_dirs`, but ranks by a blend of visit count and recency
+/// instead of raw count: the count is multiplied by 4 within the last hour,
+/// 2 within the last day, 0.5 within the last week and 0.25 after that, so
+/// 20 visits this morning (score 40) outrank 100 visits last year (score 25).
+pub fn frecent_dirs(db_path: &PathBuf, limit: i32, excludes: &ExcludeList) -> Result<Vec<DirectoryEntry>> {
+    let conn = Connection::open(db_path)?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT path, COUNT(*) as visits,
+                datetime(MAX(timestamp), 'localtime') as last_visited,
+                COUNT(*) * {FRECENCY_CASE} as score
+         FROM directory_history
+         GROUP BY path
+         ORDER BY score DESC
+         LIMIT ?1"
+    ))?;
+
+    let entries = stmt.query_map([limit], |row| {
+        Ok(DirectoryEntry {
+            path: row.get(0)?,
+            visits: Some(row.get(1)?),
+            timestamp: Some(row.get(2)?),
+            score: Some(row.get(3)?),
+        })
+    })?.collect::<Result<Vec<_>>>()?;
+
+    let (kept, excluded) = split_excluded(entries, excludes, |e| &e.path);
+    purge_excluded(&conn, "directory_history", &excluded)?;
+    Ok(kept)
+}
+
+/// `frecent_dirs`'s counterpart over `file_history`.
+pub fn frecent_files(db_path: &PathBuf, limit: i32, excludes: &ExcludeList) -> Result<Vec<FileEntry>> {
+    let conn = Connection::open(db_path)?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT path, file_type, action, COUNT(*) as opens,
+                datetime(MAX(timestamp), 'localtime') as last_opened,
+                COUNT(*) * {FRECENCY_CASE} as score
+         FROM file_history
+         GROUP BY path, file_type, action
+         ORDER BY score DESC
+         LIMIT ?1"
+    ))?;
+
+    let entries = stmt.query_map([limit], |row| {
+        Ok(FileEntry {
+            path: row.get(0)?,
+            file_type: row.get(1)?,
+            action: row.get(2)?,
+            opens: Some(row.get(3)?),
+            timestamp: Some(row.get(4)?),
+            score: Some(row.get(5)?),
+        })
+    })?.collect::<Result<Vec<_>>>()?;
+
+    let (kept, excluded) = split_excluded(entries, excludes, |e| &e.path);
+    purge_excluded(&conn, "file_history", &excluded)?;
+    Ok(kept)
+}
+
+/// Candidates for the `jump` command: a substring match over
+/// `directory_history`, like `search_history`, ranked by the same
+/// frecency score as `frecent_dirs` so the single best match wins.
+pub fn jump_dirs(db_path: &PathBuf, query: &str, excludes: &ExcludeList) -> Result<Vec<DirectoryEntry>> {
+    let conn = Connection::open(db_path)?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT path, COUNT(*) as visits,
+                datetime(MAX(timestamp), 'localtime') as last_visited,
+                COUNT(*) * {FRECENCY_CASE} as score
+         FROM directory_history
+         WHERE path LIKE ?1
+         GROUP BY path
+         ORDER BY score DESC"
+    ))?;
+
+    let entries = stmt.query_map([format!("%{}%", query)], |row| {
+        Ok(DirectoryEntry {
+            path: row.get(0)?,
+            visits: Some(row.get(1)?),
+            timestamp: Some(row.get(2)?),
+            score: Some(row.get(3)?),
+        })
+    })?.collect::<Result<Vec<_>>>()?;
+
+    let (kept, excluded) = split_excluded(entries, excludes, |e| &e.path);
+    purge_excluded(&conn, "directory_history", &excluded)?;
+    Ok(kept)
+}
+
+pub fn file_stats(db_path: &PathBuf) -> Result<Vec<FileStats>> {
+    let conn = Connection::open(db_path)?;
+    let mut stmt = conn.prepare(
+        "SELECT file_type, action, COUNT(*) as opens
+         FROM file_history
+         GROUP BY file_type, action
+         ORDER BY opens DESC"
+    )?;
+
+    let entries = stmt.query_map([], |row| {
+        Ok(FileStats {
+            file_type: row.get(0)?,
+            action: row.get(1)?,
+            opens: row.get(2)?,
+        })
+    })?;
+
+    entries.collect()
+}
+
+pub fn search_history(db_path: &PathBuf, query: &str, excludes: &ExcludeList) -> Result<SearchResult> {
+    let conn = Connection::open(db_path)?;
+
+    // Search directories
+    let mut dir_stmt = conn.prepare(
+        "SELECT DISTINCT path, COUNT(*) as visits
+         FROM directory_history
+         WHERE path LIKE ?1
+         GROUP BY path
+         ORDER BY visits DESC"
+    )?;
+
+    let dir_entries = dir_stmt.query_map([format!("%{}%", query)], |row| {
+        Ok(DirectoryEntry {
+            path: row.get(0)?,
+            visits: Some(row.get(1)?),
+            timestamp: None,
+            score: None,
+        })
+    })?.collect::<Result<Vec<_>>>()?;
+
+    // Search files
+    let mut file_stmt = conn.prepare(
+        "SELECT path, file_type, action, COUNT(*) as opens
+         FROM file_history
+         WHERE path LIKE ?1
+         GROUP BY path, file_type, action
+         ORDER BY opens DESC"
+    )?;
+
+    let file_entries = file_stmt.query_map([format!("%{}%", query)], |row| {
+        Ok(FileEntry {
+            path: row.get(0)?,
+            file_type: row.get(1)?,
+            action: row.get(2)?,
+            opens: Some(row.get(3)?),
+            timestamp: None,
+            score: None,
+        })
+    })?.collect::<Result<Vec<_>>>()?;
+
+    let (dirs_kept, dirs_excluded) = split_excluded(dir_entries, excludes, |e| &e.path);
+    let (files_kept, files_excluded) = split_excluded(file_entries, excludes, |e| &e.path);
+    purge_excluded(&conn, "directory_history", &dirs_excluded)?;
+    purge_excluded(&conn, "file_history", &files_excluded)?;
+
+    Ok(SearchResult {
+        directories: dirs_kept,
+        files: files_kept,
+    })
+}
@@ -1,199 +1,39 @@
-use rusqlite::{Connection, Result};
+use rusqlite::Result;
 use std::collections::HashSet;
 use std::env;
 use std::path::PathBuf;
 use std::io::{IsTerminal, Write};
 use std::process::{Command, Stdio};
-use serde::{Deserialize, Serialize};
+use serde::Serialize;
 use colored_json::prelude::*;
 
-#[derive(Debug, Serialize, Deserialize)]
-struct DirectoryEntry {
-    path: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    timestamp: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    visits: Option<i32>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct FileEntry {
-    path: String,
-    file_type: String,
-    action: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    timestamp: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    opens: Option<i32>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct FileStats {
-    file_type: String,
-    action: String,
-    opens: i32,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct SearchResult {
-    directories: Vec<DirectoryEntry>,
-    files: Vec<FileEntry>,
-}
-
-fn get_default_db_path() -> PathBuf {
-    let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    PathBuf::from(home).join(".fzf.db")
-}
-
-fn recent_dirs(db_path: &PathBuf, limit: i32) -> Result<Vec<DirectoryEntry>> {
-    let conn = Connection::open(db_path)?;
-    let mut stmt = conn.prepare(
-        "SELECT path, datetime(timestamp, 'localtime') as visited
-         FROM (
-             SELECT * FROM directory_history 
-             ORDER BY timestamp DESC 
-             LIMIT ?1
-         ) 
-         ORDER BY timestamp ASC"
-    )?;
-
-    let entries = stmt.query_map([limit], |row| {
-        Ok(DirectoryEntry {
-            path: row.get(0)?,
-            timestamp: Some(row.get(1)?),
-            visits: None,
-        })
-    })?;
-    
-    entries.collect()
-}
-
-fn recent_files(db_path: &PathBuf, limit: i32) -> Result<Vec<FileEntry>> {
-    let conn = Connection::open(db_path)?;
-    let mut stmt = conn.prepare(
-        "SELECT path, file_type, action, datetime(timestamp, 'localtime') as opened
-         FROM (
-             SELECT * FROM file_history 
-             ORDER BY timestamp DESC 
-             LIMIT ?1
-         ) 
-         ORDER BY timestamp ASC"
-    )?;
-    
-    let entries = stmt.query_map([limit], |row| {
-        Ok(FileEntry {
-            path: row.get(0)?,
-            file_type: row.get(1)?,
-            action: row.get(2)?,
-            timestamp: Some(row.get(3)?),
-            opens: None,
-        })
-    })?;
-    
-    entries.collect()
-}
-
-fn popular_dirs(db_path: &PathBuf, limit: i32) -> Result<Vec<DirectoryEntry>> {
-    let conn = Connection::open(db_path)?;
-    let mut stmt = conn.prepare(
-        "SELECT path, COUNT(*) as visits, 
-                datetime(MAX(timestamp), 'localtime') as last_visited
-         FROM directory_history 
-         GROUP BY path 
-         ORDER BY visits DESC 
-         LIMIT ?1"
-    )?;
-    
-    let entries = stmt.query_map([limit], |row| {
-        Ok(DirectoryEntry {
-            path: row.get(0)?,
-            visits: Some(row.get(1)?),
-            timestamp: Some(row.get(2)?),
-        })
-    })?;
-    
-    entries.collect()
-}
-
-fn file_stats(db_path: &PathBuf) -> Result<Vec<FileStats>> {
-    let conn = Connection::open(db_path)?;
-    let mut stmt = conn.prepare(
-        "SELECT file_type, action, COUNT(*) as opens
-         FROM file_history 
-         GROUP BY file_type, action 
-         ORDER BY opens DESC"
-    )?;
-    
-    let entries = stmt.query_map([], |row| {
-        Ok(FileStats {
-            file_type: row.get(0)?,
-            action: row.get(1)?,
-            opens: row.get(2)?,
-        })
-    })?;
-    
-    entries.collect()
-}
+mod db;
 
-fn search_history(db_path: &PathBuf, query: &str) -> Result<SearchResult> {
-    let conn = Connection::open(db_path)?;
-    
-    // Search directories
-    let mut dir_stmt = conn.prepare(
-        "SELECT DISTINCT path, COUNT(*) as visits
-         FROM directory_history 
-         WHERE path LIKE ?1
-         GROUP BY path
-         ORDER BY visits DESC"
-    )?;
-    
-    let dir_entries = dir_stmt.query_map([format!("%{}%", query)], |row| {
-        Ok(DirectoryEntry {
-            path: row.get(0)?,
-            visits: Some(row.get(1)?),
-            timestamp: None,
-        })
-    })?;
-    
-    // Search files
-    let mut file_stmt = conn.prepare(
-        "SELECT path, file_type, action, COUNT(*) as opens
-         FROM file_history 
-         WHERE path LIKE ?1
-         GROUP BY path, file_type, action
-         ORDER BY opens DESC"
-    )?;
-    
-    let file_entries = file_stmt.query_map([format!("%{}%", query)], |row| {
-        Ok(FileEntry {
-            path: row.get(0)?,
-            file_type: row.get(1)?,
-            action: row.get(2)?,
-            opens: Some(row.get(3)?),
-            timestamp: None,
-        })
-    })?;
-    
-    Ok(SearchResult {
-        directories: dir_entries.collect::<Result<Vec<_>>>()?,
-        files: file_entries.collect::<Result<Vec<_>>>()?,
-    })
-}
+use db::{
+    cleanup_database, duplicates, file_stats, frecent_dirs, frecent_files, get_default_db_path,
+    get_default_excludes_path, jump_dirs, load_excludes, popular_dirs, recent_dirs, recent_files,
+    search_history, DirectoryEntry, ExcludeList, DEFAULT_MAX_AGE, DEFAULT_RETENTION_DAYS,
+};
 
-fn change_to_dir(db_path: &PathBuf, limit: i32) -> Result<(), Box<dyn std::error::Error>> {
+fn change_to_dir(
+    db_path: &PathBuf,
+    limit: i32,
+    excludes: &ExcludeList,
+    preview: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     // Get recent directories from database
-    let dirs: Vec<_> = recent_dirs(db_path, limit)?.into_iter().rev().collect();
-    
+    let dirs: Vec<_> = recent_dirs(db_path, limit, excludes)?.into_iter().rev().collect();
+
     if dirs.is_empty() {
         eprintln!("No recent directories found in history");
         return Ok(());
     }
-    
+
     // Create a list of directory paths for fzf, expanding to absolute paths
     // Use a HashSet to track seen paths and avoid duplicates
     let mut seen = HashSet::new();
-    let mut dir_paths: Vec<String> = Vec::new();
-    
+    let mut dir_rows: Vec<(String, Option<i32>, Option<String>)> = Vec::new();
+
     for d in &dirs {
         let path = PathBuf::from(&d.path);
         // Try to canonicalize the path to get absolute path
@@ -215,45 +55,66 @@ fn change_to_dir(db_path: &PathBuf, limit: i32) -> Result<(), Box<dyn std::error
                 }
             }
         };
-        
+
         // Only add if we haven't seen this path before
         if let Some(abs_path) = abs_path_opt {
             if seen.insert(abs_path.clone()) {
-                dir_paths.push(abs_path);
+                dir_rows.push((abs_path, d.visits, d.timestamp.clone()));
             }
         }
     }
-    
-    if dir_paths.is_empty() {
+
+    if dir_rows.is_empty() {
         eprintln!("No valid directories found in history");
         return Ok(());
     }
-    
+
     // Launch fzf with the directory paths
-    let mut fzf = Command::new("fzf")
-        .arg("--height=40%")
-        .arg("--reverse")
+    let mut fzf_cmd = Command::new("fzf");
+    fzf_cmd.arg("--height=40%").arg("--reverse");
+
+    if preview {
+        fzf_cmd
+            .arg("--delimiter=\t")
+            .arg("--with-nth=1")
+            .arg(format!("--preview={}", preview_command_for_dir()));
+    }
+
+    let mut fzf = fzf_cmd
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::inherit())
         .spawn()?;
-    
-    // Send directory paths to fzf's stdin
+
+    // Send directory paths to fzf's stdin, with visit count/timestamp metadata
+    // tagging along as hidden columns when preview mode needs them for display.
+    // recent_dirs does not count visits, so the visit count column is empty.
     if let Some(mut stdin) = fzf.stdin.take() {
-        for path in &dir_paths {
-            writeln!(stdin, "{}", path)?;
+        for (path, visits, timestamp) in &dir_rows {
+            if preview {
+                writeln!(
+                    stdin,
+                    "{}\t{}\t{}",
+                    path,
+                    visits.map(|v| v.to_string()).unwrap_or_default(),
+                    timestamp.as_deref().unwrap_or("")
+                )?;
+            } else {
+                writeln!(stdin, "{}", path)?;
+            }
         }
     }
-    
+
     // Wait for fzf to finish and get the selected directory
     let output = fzf.wait_with_output()?;
-    
+
     if output.status.success() {
-        let selected_dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        
+        let selected_line = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let selected_dir = selected_line.split('\t').next().unwrap_or("").to_string();
+
         if !selected_dir.is_empty() {
             let path = PathBuf::from(&selected_dir);
-            
+
             // The path should already be absolute from our processing above,
             // but let's make sure it exists
             if path.exists() && path.is_dir() {
@@ -268,24 +129,29 @@ fn change_to_dir(db_path: &PathBuf, limit: i32) -> Result<(), Box<dyn std::error
         // User cancelled fzf (Ctrl+C or Escape)
         std::process::exit(1);
     }
-    
+
     Ok(())
 }
 
-fn change_to_file(db_path: &PathBuf, limit: i32) -> Result<(), Box<dyn std::error::Error>> {
+fn change_to_file(
+    db_path: &PathBuf,
+    limit: i32,
+    excludes: &ExcludeList,
+    preview: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     // Get recent files from database
-    let files: Vec<_> = recent_files(db_path, limit)?.into_iter().rev().collect();
-    
+    let files: Vec<_> = recent_files(db_path, limit, excludes)?.into_iter().rev().collect();
+
     if files.is_empty() {
         eprintln!("No recent files found in history");
         return Ok(());
     }
-    
+
     // Create a list of file paths for fzf, expanding to absolute paths
     // Use a HashSet to track seen paths and avoid duplicates
     let mut seen = HashSet::new();
-    let mut file_paths: Vec<String> = Vec::new();
-    
+    let mut file_rows: Vec<(String, Option<i32>, Option<String>)> = Vec::new();
+
     for f in &files {
         let path = PathBuf::from(&f.path);
         // Try to canonicalize the path to get absolute path
@@ -307,45 +173,66 @@ fn change_to_file(db_path: &PathBuf, limit: i32) -> Result<(), Box<dyn std::erro
                 }
             }
         };
-        
+
         // Only add if we haven't seen this path before
         if let Some(abs_path) = abs_path_opt {
             if seen.insert(abs_path.clone()) {
-                file_paths.push(abs_path);
+                file_rows.push((abs_path, f.opens, f.timestamp.clone()));
             }
         }
     }
-    
-    if file_paths.is_empty() {
+
+    if file_rows.is_empty() {
         eprintln!("No valid files found in history");
         return Ok(());
     }
-    
+
     // Launch fzf with the file paths
-    let mut fzf = Command::new("fzf")
-        .arg("--height=40%")
-        .arg("--reverse")
+    let mut fzf_cmd = Command::new("fzf");
+    fzf_cmd.arg("--height=40%").arg("--reverse");
+
+    if preview {
+        fzf_cmd
+            .arg("--delimiter=\t")
+            .arg("--with-nth=1")
+            .arg(format!("--preview={}", preview_command_for_file()));
+    }
+
+    let mut fzf = fzf_cmd
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::inherit())
         .spawn()?;
-    
-    // Send file paths to fzf's stdin
+
+    // Send file paths to fzf's stdin, with open count/timestamp metadata
+    // tagging along as hidden columns when preview mode needs them for display.
+    // recent_files does not count opens, so the open count column is empty.
     if let Some(mut stdin) = fzf.stdin.take() {
-        for path in &file_paths {
-            writeln!(stdin, "{}", path)?;
+        for (path, opens, timestamp) in &file_rows {
+            if preview {
+                writeln!(
+                    stdin,
+                    "{}\t{}\t{}",
+                    path,
+                    opens.map(|o| o.to_string()).unwrap_or_default(),
+                    timestamp.as_deref().unwrap_or("")
+                )?;
+            } else {
+                writeln!(stdin, "{}", path)?;
+            }
         }
     }
-    
+
     // Wait for fzf to finish and get the selected file
     let output = fzf.wait_with_output()?;
-    
+
     if output.status.success() {
-        let selected_file = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        
+        let selected_line = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let selected_file = selected_line.split('\t').next().unwrap_or("").to_string();
+
         if !selected_file.is_empty() {
             let path = PathBuf::from(&selected_file);
-            
+
             // The path should already be absolute from our processing above,
             // but let's make sure it exists
             if path.exists() && path.is_file() {
@@ -360,10 +247,66 @@ fn change_to_file(db_path: &PathBuf, limit: i32) -> Result<(), Box<dyn std::erro
         // User cancelled fzf (Ctrl+C or Escape)
         std::process::exit(1);
     }
-    
+
     Ok(())
 }
 
+/// Resolves `jump`'s ranked candidates to a single path: the top candidate
+/// that still exists on disk, skipping the current directory once so
+/// repeated jumps cycle instead of no-op (as zoxide's `z` does).
+fn resolve_jump(candidates: &[DirectoryEntry]) -> Option<String> {
+    let cwd = env::current_dir().ok().map(|p| p.to_string_lossy().to_string());
+    let mut skipped_cwd = false;
+
+    for candidate in candidates {
+        let abs_path = match PathBuf::from(&candidate.path).canonicalize() {
+            Ok(abs) => abs.to_string_lossy().to_string(),
+            Err(_) => continue, // candidate no longer exists on disk
+        };
+
+        if !skipped_cwd && cwd.as_deref() == Some(abs_path.as_str()) {
+            skipped_cwd = true;
+            continue;
+        }
+
+        return Some(abs_path);
+    }
+
+    None
+}
+
+/// True if `cmd` resolves to something on `PATH`, used to prefer a nicer
+/// preview tool (eza/tree, bat) but fall back to plain ls/head everywhere.
+fn command_exists(cmd: &str) -> bool {
+    Command::new("which")
+        .arg(cmd)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+fn preview_command_for_dir() -> String {
+    let listing = if command_exists("eza") {
+        "eza -la --color=always {1}"
+    } else if command_exists("tree") {
+        "tree -C {1}"
+    } else {
+        "ls -la {1}"
+    };
+    format!("echo 'visits={{2}} last={{3}}'; {listing}")
+}
+
+fn preview_command_for_file() -> String {
+    let contents = if command_exists("bat") {
+        "bat --color=always --style=numbers {1}"
+    } else {
+        "head -n 100 {1}"
+    };
+    format!("echo 'opens={{2}} last={{3}}'; {contents}")
+}
+
 fn print_json<T: Serialize>(data: &T, use_color: bool) -> Result<(), Box<dyn std::error::Error>> {
     let json_string = serde_json::to_string_pretty(data)?;
     
@@ -385,23 +328,38 @@ fn print_usage() {
     println!("  fzf-nav [--db-path <path>] [--no-color] recent-dirs [limit]     # Show recent directory visits (default: 50)");
     println!("  fzf-nav [--db-path <path>] [--no-color] recent-files [limit]    # Show recent file opens (default: 50)");
     println!("  fzf-nav [--db-path <path>] [--no-color] popular-dirs [limit]    # Show most visited directories (default: 50)");
+    println!("  fzf-nav [--db-path <path>] [--no-color] frecent-dirs [limit]    # Show directories ranked by frequency+recency (default: 50)");
+    println!("  fzf-nav [--db-path <path>] [--no-color] frecent-files [limit]   # Show files ranked by frequency+recency (default: 50)");
     println!("  fzf-nav [--db-path <path>] [--no-color] file-stats              # Show file type statistics");
+    println!("  fzf-nav [--db-path <path>] [--no-color] duplicates              # Show groups of files with identical content");
     println!("  fzf-nav [--db-path <path>] [--no-color] search <query>          # Search history");
-    println!("  fzf-nav [--db-path <path>] change-to-dir [limit]                # Interactive directory selection with fzf (default: 100)");
-    println!("  fzf-nav [--db-path <path>] change-to-file [limit]               # Interactive file selection with fzf (default: 100)");
+    println!("  fzf-nav [--db-path <path>] jump <query>                         # Print the single best-matching directory");
+    println!("  fzf-nav [--db-path <path>] [--preview] change-to-dir [limit]    # Interactive directory selection with fzf (default: 100)");
+    println!("  fzf-nav [--db-path <path>] [--preview] change-to-file [limit]   # Interactive file selection with fzf (default: 100)");
+    println!("  fzf-nav [--db-path <path>] cleanup                              # Purge stale, expired, and over-capacity history");
     println!("  fzf-nav help                                                    # Show this help message");
     println!();
     println!("Options:");
-    println!("  --db-path <path>    Path to the database file (default: ~/.fzf.db)");
-    println!("  --no-color          Disable colored JSON output");
+    println!("  --db-path <path>        Path to the database file (default: ~/.fzf.db)");
+    println!("  --no-color              Disable colored JSON output");
+    println!("  --retention-days <n>    Days of history cleanup keeps before purging (default: {})", DEFAULT_RETENTION_DAYS);
+    println!("  --max-age <n>           Total visit count cleanup allows before dropping the least-visited paths (default: {})", DEFAULT_MAX_AGE);
+    println!("  --exclude <glob>        Suppress paths matching this glob (repeatable); also read from ~/.fzf-nav/excludes");
+    println!("  --preview               Show a preview pane in change-to-dir/change-to-file");
 }
 
-fn parse_args(args: &[String]) -> (Option<PathBuf>, bool, Vec<String>) {
+type ParsedArgs = (Option<PathBuf>, bool, Option<i64>, Option<f64>, Vec<String>, bool, Vec<String>);
+
+fn parse_args(args: &[String]) -> ParsedArgs {
     let mut db_path = None;
     let mut use_color = true;
+    let mut retention_days = None;
+    let mut max_age = None;
+    let mut exclude_patterns = Vec::new();
+    let mut preview = false;
     let mut remaining_args = Vec::new();
     let mut i = 1; // Skip program name
-    
+
     while i < args.len() {
         match args[i].as_str() {
             "--db-path" => {
@@ -417,14 +375,57 @@ fn parse_args(args: &[String]) -> (Option<PathBuf>, bool, Vec<String>) {
                 use_color = false;
                 i += 1;
             },
+            "--retention-days" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].parse() {
+                        Ok(days) => retention_days = Some(days),
+                        Err(_) => {
+                            eprintln!("Error: --retention-days requires a number");
+                            std::process::exit(1);
+                        }
+                    }
+                    i += 2;
+                } else {
+                    eprintln!("Error: --retention-days requires a value");
+                    std::process::exit(1);
+                }
+            },
+            "--max-age" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].parse() {
+                        Ok(age) => max_age = Some(age),
+                        Err(_) => {
+                            eprintln!("Error: --max-age requires a number");
+                            std::process::exit(1);
+                        }
+                    }
+                    i += 2;
+                } else {
+                    eprintln!("Error: --max-age requires a value");
+                    std::process::exit(1);
+                }
+            },
+            "--exclude" => {
+                if i + 1 < args.len() {
+                    exclude_patterns.push(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Error: --exclude requires a value");
+                    std::process::exit(1);
+                }
+            },
+            "--preview" => {
+                preview = true;
+                i += 1;
+            },
             _ => {
                 remaining_args.push(args[i].clone());
                 i += 1;
             }
         }
     }
-    
-    (db_path, use_color, remaining_args)
+
+    (db_path, use_color, retention_days, max_age, exclude_patterns, preview, remaining_args)
 }
 
 fn main() {
@@ -435,21 +436,22 @@ fn main() {
         return;
     }
     
-    let (custom_db_path, use_color, remaining_args) = parse_args(&args);
+    let (custom_db_path, use_color, retention_days, max_age, exclude_patterns, preview, remaining_args) = parse_args(&args);
     let db_path = custom_db_path.unwrap_or_else(get_default_db_path);
-    
+    let excludes = load_excludes(&exclude_patterns, &get_default_excludes_path());
+
     if remaining_args.is_empty() {
         print_usage();
         return;
     }
-    
+
     let result = match remaining_args[0].as_str() {
         "recent-dirs" => {
             let limit = remaining_args.get(1)
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(50);
             
-            match recent_dirs(&db_path, limit) {
+            match recent_dirs(&db_path, limit, &excludes) {
                 Ok(dirs) => {
                     if let Err(e) = print_json(&dirs, use_color) {
                         eprintln!("JSON output error: {}", e);
@@ -465,7 +467,7 @@ fn main() {
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(50);
             
-            match recent_files(&db_path, limit) {
+            match recent_files(&db_path, limit, &excludes) {
                 Ok(files) => {
                     if let Err(e) = print_json(&files, use_color) {
                         eprintln!("JSON output error: {}", e);
@@ -481,7 +483,7 @@ fn main() {
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(50);
             
-            match popular_dirs(&db_path, limit) {
+            match popular_dirs(&db_path, limit, &excludes) {
                 Ok(dirs) => {
                     if let Err(e) = print_json(&dirs, use_color) {
                         eprintln!("JSON output error: {}", e);
@@ -492,6 +494,38 @@ fn main() {
             }
         },
         
+        "frecent-dirs" => {
+            let limit = remaining_args.get(1)
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(50);
+
+            match frecent_dirs(&db_path, limit, &excludes) {
+                Ok(dirs) => {
+                    if let Err(e) = print_json(&dirs, use_color) {
+                        eprintln!("JSON output error: {}", e);
+                    }
+                    Ok(())
+                },
+                Err(e) => Err(e),
+            }
+        },
+
+        "frecent-files" => {
+            let limit = remaining_args.get(1)
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(50);
+
+            match frecent_files(&db_path, limit, &excludes) {
+                Ok(files) => {
+                    if let Err(e) = print_json(&files, use_color) {
+                        eprintln!("JSON output error: {}", e);
+                    }
+                    Ok(())
+                },
+                Err(e) => Err(e),
+            }
+        },
+
         "file-stats" => {
             match file_stats(&db_path) {
                 Ok(stats) => {
@@ -504,6 +538,18 @@ fn main() {
             }
         },
         
+        "duplicates" => {
+            match duplicates(&db_path) {
+                Ok(groups) => {
+                    if let Err(e) = print_json(&groups, use_color) {
+                        eprintln!("JSON output error: {}", e);
+                    }
+                    Ok(())
+                },
+                Err(e) => Err(e),
+            }
+        },
+
         "search" => {
             if remaining_args.len() < 2 {
                 eprintln!("Error: search requires a query string");
@@ -512,7 +558,7 @@ fn main() {
             }
             
             let query = &remaining_args[1];
-            match search_history(&db_path, query) {
+            match search_history(&db_path, query, &excludes) {
                 Ok(results) => {
                     if let Err(e) = print_json(&results, use_color) {
                         eprintln!("JSON output error: {}", e);
@@ -523,12 +569,35 @@ fn main() {
             }
         },
         
+        "jump" => {
+            if remaining_args.len() < 2 {
+                eprintln!("Error: jump requires a query string");
+                print_usage();
+                return;
+            }
+
+            let query = &remaining_args[1];
+            match jump_dirs(&db_path, query, &excludes) {
+                Ok(candidates) => {
+                    match resolve_jump(&candidates) {
+                        Some(path) => println!("{}", path),
+                        None => {
+                            eprintln!("No matching directory found for {:?}", query);
+                            std::process::exit(1);
+                        }
+                    }
+                    return;
+                },
+                Err(e) => Err(e),
+            }
+        },
+
         "change-to-dir" => {
             let limit = remaining_args.get(1)
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(100);
             
-            if let Err(e) = change_to_dir(&db_path, limit) {
+            if let Err(e) = change_to_dir(&db_path, limit, &excludes, preview) {
                 eprintln!("Error: {}", e);
                 std::process::exit(1);
             }
@@ -540,13 +609,26 @@ fn main() {
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(100);
             
-            if let Err(e) = change_to_file(&db_path, limit) {
+            if let Err(e) = change_to_file(&db_path, limit, &excludes, preview) {
                 eprintln!("Error: {}", e);
                 std::process::exit(1);
             }
             return; // Don't process result further since change_to_file handles its own output
         },
         
+        "cleanup" => {
+            let retention_days = retention_days.unwrap_or(DEFAULT_RETENTION_DAYS);
+            let max_age = max_age.unwrap_or(DEFAULT_MAX_AGE);
+
+            match cleanup_database(&db_path, retention_days, max_age, &excludes) {
+                Ok(()) => {
+                    println!("Cleaned up database at {:?}", db_path);
+                    Ok(())
+                },
+                Err(e) => Err(e),
+            }
+        },
+
         "help" | "--help" | "-h" => {
             print_usage();
             return;